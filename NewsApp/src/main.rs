@@ -1,16 +1,60 @@
 use serde::Deserialize;
 use enum_iterator::{all, cardinality, Sequence};
 use eframe::egui::{self, RichText};
-use std::{thread, time, error::Error, sync::mpsc, env};
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use std::{time, error::Error, sync::mpsc, env};
+use tokio::sync::mpsc as async_mpsc;
+
+mod storage;
+mod notifications;
+use storage::{Store, StoredArticle};
+
+#[derive(PartialEq, Clone)]
+enum Source {
+    NewsApi { categories : Vec<String> },
+    Feed { url : String },
+}
 
-#[derive(PartialEq)]
-struct NewsReportConfig{
-    selected_categories : Vec<String>,
-    polling_interval : u64,
+#[derive(Sequence, Debug, Clone, Copy, PartialEq)]
+enum SortOrder {
+    Relevancy,
+    Popularity,
+    PublishedAt,
+}
+
+impl SortOrder {
+    fn to_string(&self) -> String {
+        match self {
+            SortOrder::Relevancy => "Relevancy",
+            SortOrder::Popularity => "Popularity",
+            SortOrder::PublishedAt => "Published date",
+        }.to_string()
+    }
+
+    fn query_value(&self) -> &'static str {
+        match self {
+            SortOrder::Relevancy => "relevancy",
+            SortOrder::Popularity => "popularity",
+            SortOrder::PublishedAt => "publishedAt",
+        }
+    }
+}
+
+// A non-empty `query` switches the fetcher over to NewsAPI's `/everything`
+// endpoint; `from`/`to` are `YYYY-MM-DD` and left out of the query string when empty.
+#[derive(PartialEq, Clone, Debug)]
+struct SearchFilter {
+    query : String,
+    from : String,
+    to : String,
+    sort_by : SortOrder,
 }
 
 #[derive(Deserialize, Debug)]
 struct Articles {
+    #[serde(rename = "totalResults")]
+    total_results : Option<u32>,
     articles: Vec<Article>
 }
 
@@ -20,7 +64,7 @@ struct Article {
     url : String,
 }
 
-#[derive(Sequence, Debug)]
+#[derive(Sequence, Debug, Clone, Copy, PartialEq)]
 enum Categories{
     Business,
     Entertainment,
@@ -45,75 +89,508 @@ impl Categories {
     }
 }
 
+// ISO country codes NewsAPI's `top-headlines` accepts, plus a `Zz` "worldwide"
+// variant that drops the country filter in favour of the `/everything` endpoint.
+#[derive(Sequence, Debug, Clone, Copy, PartialEq)]
+enum Country{
+    Us,
+    Gb,
+    Ca,
+    Au,
+    De,
+    Fr,
+    In,
+    Jp,
+    Cn,
+    Br,
+    Za,
+    Zz,
+}
+
+impl Country {
+    fn to_string(&self) -> String {
+        match self {
+            Country::Us => "United States",
+            Country::Gb => "United Kingdom",
+            Country::Ca => "Canada",
+            Country::Au => "Australia",
+            Country::De => "Germany",
+            Country::Fr => "France",
+            Country::In => "India",
+            Country::Jp => "Japan",
+            Country::Cn => "China",
+            Country::Br => "Brazil",
+            Country::Za => "South Africa",
+            Country::Zz => "Worldwide",
+        }.to_string()
+    }
+
+    fn code(&self) -> &'static str {
+        match self {
+            Country::Us => "us",
+            Country::Gb => "gb",
+            Country::Ca => "ca",
+            Country::Au => "au",
+            Country::De => "de",
+            Country::Fr => "fr",
+            Country::In => "in",
+            Country::Jp => "jp",
+            Country::Cn => "cn",
+            Country::Br => "br",
+            Country::Za => "za",
+            Country::Zz => "",
+        }
+    }
+}
+
+// What a named feed tab shows. `Search` carries its own keyword query so several
+// searches can't collide; categories and the unfiltered `General` stream are
+// singletons per config.
+#[derive(PartialEq, Clone, Debug)]
+enum FeedKind {
+    General,
+    Category(Categories),
+    Search(SearchFilter),
+    Feed(String),
+}
+
+impl FeedKind {
+    fn label(&self) -> String {
+        match self {
+            FeedKind::General => "General".to_string(),
+            FeedKind::Category(category) => category.to_string(),
+            FeedKind::Search(filter) => format!("Search: {}", filter.query),
+            FeedKind::Feed(url) => format!("Feed: {}", url),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone)]
+struct FeedDef {
+    kind : FeedKind,
+    polling_interval : u64,
+}
+
+#[derive(PartialEq, Clone)]
+struct NewsReportConfig{
+    feeds : Vec<FeedDef>,
+    country : Country,
+    quiet : bool,
+}
+
+struct FeedUpdate {
+    kind : FeedKind,
+    page : u32,
+    result : Result<Articles, String>,
+}
+
+
+// Pure query-builder pulled out of `get_newsapi_articles` so the `Zz` ("worldwide") vs.
+// per-country branch can be unit tested without an API key or network access.
+fn build_newsapi_url( list_of_desired_categories : &[String], country : Country, page : u32, api_key : &str ) -> Result<reqwest::Url, Box<dyn Error>>{
+    let url = match country {
+        Country::Zz => {
+            let q = if list_of_desired_categories.is_empty() {
+                "news".to_string()
+            } else {
+                list_of_desired_categories.join(" OR ")
+            };
+            let params = [
+                ("q", q),
+                ("apiKey", api_key.to_string()),
+                ("page", page.to_string()),
+            ];
+            reqwest::Url::parse_with_params("https://newsapi.org/v2/everything", &params)?
+        },
+        _ => {
+            let mut params = vec![
+                ("country", country.code().to_string()),
+                ("apiKey", api_key.to_string()),
+                ("page", page.to_string()),
+            ];
+            for category in list_of_desired_categories {
+                params.push(("category", category.clone()));
+            }
+            reqwest::Url::parse_with_params("https://newsapi.org/v2/top-headlines", &params)?
+        },
+    };
+    Ok(url)
+}
+
+async fn get_newsapi_articles( list_of_desired_categories : Vec<String>, country : Country, page : u32 ) -> Result<Articles, Box<dyn Error>>{
+    let api_key = env::var("NEWS_API_KEY")
+        .map_err(|_| "NEWS_API_KEY is not set in the environment")?;
+    let query_addr = build_newsapi_url(&list_of_desired_categories, country, page, &api_key)?;
+
+    let response = reqwest::get(query_addr).await?.text().await?;
+    let articles : Articles = serde_json::from_str(&response)?;
+    Ok(articles)
+}
 
-fn get_articles( list_of_desired_categories : Vec<String> ) -> Result<Articles, Box<dyn Error>>{
-    let mut api_key = String::new();
-    match env::var("NEWS_API_KEY") {
-        Ok(key) => api_key = key,
-        Err(e) => panic!("News API key couldn't be found in the environment")
+// Pull-based reader shared by RSS `<item>` and Atom `<entry>` elements: track the
+// current element name and flush an `Article` whenever the item/entry closes.
+// Split out of `parse_feed` so the parsing logic can be unit tested against sample
+// RSS/Atom strings without a network round-trip.
+fn parse_feed_xml( xml : &str ) -> Result<Vec<Article>, Box<dyn Error>>{
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut articles = Vec::new();
+    let mut current_element = String::new();
+    let mut title = String::new();
+    let mut url = String::new();
+    let mut in_item = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = e.name();
+                let name = std::str::from_utf8(name.as_ref())?.to_string();
+                if name == "item" || name == "entry" {
+                    in_item = true;
+                    title.clear();
+                    url.clear();
+                } else if in_item && name == "link" {
+                    if let Some(href) = e.attributes().flatten().find(|a| a.key.as_ref() == b"href") {
+                        url = String::from_utf8(href.value.into_owned())?;
+                    }
+                }
+                current_element = name;
+            },
+            // Atom's `<link href="..."/>` is self-closing, so quick-xml reports it
+            // as `Empty` rather than `Start` + `End`; extract `href` the same way.
+            Ok(Event::Empty(e)) => {
+                let name = e.name();
+                let name = std::str::from_utf8(name.as_ref())?.to_string();
+                if in_item && name == "link" {
+                    if let Some(href) = e.attributes().flatten().find(|a| a.key.as_ref() == b"href") {
+                        url = String::from_utf8(href.value.into_owned())?;
+                    }
+                }
+            },
+            Ok(Event::Text(e)) => {
+                if in_item && current_element == "title" {
+                    title.push_str(&e.unescape()?);
+                } else if in_item && current_element == "link" && url.is_empty() {
+                    url.push_str(&e.unescape()?);
+                }
+            },
+            Ok(Event::End(e)) => {
+                let name = std::str::from_utf8(e.name().as_ref())?;
+                if name == "item" || name == "entry" {
+                    articles.push(Article{ title: title.clone(), url: url.clone() });
+                    in_item = false;
+                }
+                current_element.clear();
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(Box::new(e)),
+            _ => {}
+        }
     }
 
-    let mut list_of_categories_with_formatted = String::new();
+    Ok(articles)
+}
+
+async fn parse_feed( feed_url : &str ) -> Result<Vec<Article>, Box<dyn Error>>{
+    let response = reqwest::get(feed_url).await?.text().await?;
+    parse_feed_xml(&response)
+}
 
-    // Convert this to a lambda function
-    for category in list_of_desired_categories {
-        list_of_categories_with_formatted.push_str(&format!("&category={}", category));
+async fn get_search_articles( filter : &SearchFilter, page : u32 ) -> Result<Articles, Box<dyn Error>>{
+    let api_key = env::var("NEWS_API_KEY")
+        .map_err(|_| "NEWS_API_KEY is not set in the environment")?;
+
+    let mut params = vec![
+        ("q", filter.query.clone()),
+        ("sortBy", filter.sort_by.query_value().to_string()),
+        ("apiKey", api_key),
+        ("page", page.to_string()),
+    ];
+    if !filter.from.is_empty() {
+        params.push(("from", filter.from.clone()));
+    }
+    if !filter.to.is_empty() {
+        params.push(("to", filter.to.clone()));
     }
 
-    let query_addr = format!("https://newsapi.org/v2/top-headlines?country=gb{}&apiKey={}", list_of_categories_with_formatted, api_key);
-    let response = ureq::get(query_addr.as_str()).call()?.into_string()?;
+    let query_addr = reqwest::Url::parse_with_params("https://newsapi.org/v2/everything", &params)?;
+
+    let response = reqwest::get(query_addr).await?.text().await?;
     let articles : Articles = serde_json::from_str(&response)?;
     Ok(articles)
 }
 
+async fn get_articles( sources : &[Source], country : Country, page : u32 ) -> Result<Articles, Box<dyn Error>>{
+    let mut merged = Vec::new();
+    let mut total_results = None;
+
+    for source in sources {
+        match source {
+            Source::NewsApi { categories } => {
+                let mut fetched = get_newsapi_articles(categories.clone(), country, page).await?;
+                total_results = fetched.total_results.or(total_results);
+                merged.append(&mut fetched.articles);
+            },
+            Source::Feed { url } => {
+                let mut fetched = parse_feed(url).await?;
+                merged.append(&mut fetched);
+            },
+        }
+    }
+
+    Ok(Articles{ total_results, articles: merged })
+}
+
+async fn fetch_feed( kind : &FeedKind, country : Country, page : u32 ) -> Result<Articles, Box<dyn Error>>{
+    match kind {
+        FeedKind::General => get_articles(&[Source::NewsApi{ categories: vec![] }], country, page).await,
+        FeedKind::Category(category) => get_newsapi_articles(vec![category.to_string()], country, page).await,
+        FeedKind::Search(filter) => get_search_articles(filter, page).await,
+        FeedKind::Feed(url) => get_articles(&[Source::Feed{ url: url.clone() }], country, page).await,
+    }
+}
+
 
 // GUI definitions
 
+struct FeedTab {
+    kind : FeedKind,
+    list_of_articles : Vec<StoredArticle>,
+    current_page : u32,
+    total_results : Option<u32>,
+}
+
 struct NewsReports{
    category_flag : [bool; cardinality::<Categories>()],
+   country : Country,
    polling_interval : u64,
-   channel_to_news : mpsc::Sender<NewsReportConfig>,
-   channel_to_gui : mpsc::Receiver<Articles>,
-   list_of_articles : Vec<Article>
+   notify_flag : [bool; cardinality::<Categories>()],
+   quiet : bool,
+   search_query : String,
+   search_from : String,
+   search_to : String,
+   search_sort_by : SortOrder,
+   feed_url : String,
+   channel_to_news : async_mpsc::UnboundedSender<NewsReportConfig>,
+   channel_to_news_page : async_mpsc::UnboundedSender<(FeedKind, u32)>,
+   channel_to_gui : mpsc::Receiver<FeedUpdate>,
+   store : Store,
+   feeds : Vec<FeedTab>,
+   active_tab : usize,
+   show_history : bool,
+   last_error : Option<String>,
+}
+
+impl NewsReports {
+    fn rebuild_config(&self) -> NewsReportConfig {
+        let mut feeds = vec![FeedDef{ kind: FeedKind::General, polling_interval: self.polling_interval }];
+
+        for (index, category) in all::<Categories>().enumerate() {
+            if self.category_flag[index] {
+                feeds.push(FeedDef{ kind: FeedKind::Category(category), polling_interval: self.polling_interval });
+            }
+        }
+
+        if !self.search_query.is_empty() {
+            feeds.push(FeedDef{ kind: FeedKind::Search(SearchFilter{
+                query: self.search_query.clone(),
+                from: self.search_from.clone(),
+                to: self.search_to.clone(),
+                sort_by: self.search_sort_by,
+            }), polling_interval: self.polling_interval });
+        }
+
+        if !self.feed_url.is_empty() {
+            feeds.push(FeedDef{ kind: FeedKind::Feed(self.feed_url.clone()), polling_interval: self.polling_interval });
+        }
+
+        NewsReportConfig{
+            feeds,
+            country: self.country,
+            quiet: self.quiet,
+        }
+    }
+
+    fn reconcile_feeds(&mut self, config : &NewsReportConfig) {
+        let mut feeds = Vec::with_capacity(config.feeds.len());
+        for def in &config.feeds {
+            let existing = self.feeds.iter().find(|feed| feed.kind == def.kind);
+            feeds.push(FeedTab{
+                kind: def.kind.clone(),
+                list_of_articles: existing.map(|feed| feed.list_of_articles.clone()).unwrap_or_default(),
+                current_page: existing.map(|feed| feed.current_page).unwrap_or(1),
+                total_results: existing.and_then(|feed| feed.total_results),
+            });
+        }
+        self.feeds = feeds;
+        if self.active_tab >= self.feeds.len() {
+            self.active_tab = 0;
+        }
+    }
 }
 
 impl eframe::App for NewsReports {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
-            
-            show_menu(ui, &mut self.category_flag, &mut self.polling_interval, &mut self.channel_to_news);
-            
-            ui.heading("News Reports");
+
+            let is_update_required = show_menu(ui, &mut self.category_flag, &mut self.country, &mut self.polling_interval, &mut self.notify_flag, &mut self.quiet, &mut self.search_query, &mut self.search_from, &mut self.search_to, &mut self.search_sort_by, &mut self.feed_url);
+
+            if is_update_required {
+                let new_config = self.rebuild_config();
+                self.reconcile_feeds(&new_config);
+                self.channel_to_news.send(new_config).unwrap();
+            }
+
+            ui.horizontal(|ui| {
+                ui.heading("News Reports");
+                let history_label = if self.show_history { "Show latest" } else { "Show history" };
+                if ui.button(history_label).clicked() {
+                    self.show_history = !self.show_history;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                for (index, feed) in self.feeds.iter().enumerate() {
+                    if ui.selectable_label(self.active_tab == index, feed.kind.label()).clicked() {
+                        self.active_tab = index;
+                    }
+                }
+            });
+
             match self.channel_to_gui.try_recv() {
-                Ok(articles) => {
-                    self.list_of_articles = articles.articles;
+                Ok(FeedUpdate{ kind, page, result: Ok(articles) }) => {
+                    self.last_error = None;
+                    let mut store_error = None;
+
+                    if let Err(e) = self.store.upsert(&articles.articles) {
+                        store_error.get_or_insert(e.to_string());
+                    }
+
+                    let notify_enabled = match &kind {
+                        FeedKind::Category(category) => {
+                            let index = all::<Categories>().position(|candidate| candidate == *category).unwrap();
+                            self.notify_flag[index]
+                        },
+                        _ => false,
+                    };
+
+                    if let Some(feed) = self.feeds.iter_mut().find(|feed| feed.kind == kind) {
+                        // "New" for notification purposes means new to *this tab*, not new to the
+                        // shared store: the always-present General tab upserts most headlines first,
+                        // so checking against the store would make Category tabs never see anything
+                        // as new once General has already fetched the same story.
+                        let previously_known : std::collections::HashSet<&str> = feed.list_of_articles.iter()
+                            .map(|article| article.url.as_str())
+                            .collect();
+                        let newly_seen : Vec<&Article> = articles.articles.iter()
+                            .filter(|article| !previously_known.contains(article.url.as_str()))
+                            .collect();
+
+                        if !self.quiet && notify_enabled && !newly_seen.is_empty() {
+                            let fresh : Vec<StoredArticle> = newly_seen.iter()
+                                .filter_map(|article| match self.store.get(&article.url) {
+                                    Ok(stored) => stored,
+                                    Err(e) => { store_error.get_or_insert(e.to_string()); None },
+                                })
+                                .collect();
+                            if let Err(e) = notifications::notify_new_articles(&fresh) {
+                                eprintln!("Failed to show notification: {}", e);
+                            }
+                        }
+
+                        let fetched : Vec<StoredArticle> = articles.articles.iter()
+                            .filter_map(|article| match self.store.get(&article.url) {
+                                Ok(stored) => stored,
+                                Err(e) => { store_error.get_or_insert(e.to_string()); None },
+                            })
+                            .collect();
+                        if page <= 1 {
+                            feed.list_of_articles = fetched;
+                        } else {
+                            feed.list_of_articles.extend(fetched);
+                        }
+                        feed.current_page = page;
+                        feed.total_results = articles.total_results.or(feed.total_results);
+                    }
+
+                    if let Some(e) = store_error {
+                        self.last_error = Some(format!("Local store error: {}", e));
+                    }
+                },
+                Ok(FeedUpdate{ result: Err(fetch_error), .. }) => {
+                    self.last_error = Some(fetch_error);
                 },
                 _ => {}
             };
 
+            let displayed = if self.show_history {
+                match self.store.all() {
+                    Ok(rows) => rows,
+                    Err(e) => {
+                        self.last_error = Some(format!("Local store error: {}", e));
+                        Vec::new()
+                    },
+                }
+            } else {
+                self.feeds.get(self.active_tab).map(|feed| feed.list_of_articles.clone()).unwrap_or_default()
+            };
+
+            if let Some(error) = &self.last_error {
+                ui.colored_label(egui::Color32::from_rgb(220, 80, 80), format!("⚠ {}", error));
+            }
+
             egui::ScrollArea::vertical().show(ui, |ui| {
-                for article in self.list_of_articles.iter(){
-                    ui.label(RichText::new(format!("{}", article.title)).color(egui::Color32::from_rgb(173, 216, 230)));
-                    ui.hyperlink(article.url.to_string()).on_hover_text("Click to see the news");
+                for article in displayed.iter(){
+                    let color = if article.read {
+                        egui::Color32::from_rgb(130, 130, 130)
+                    } else {
+                        egui::Color32::from_rgb(173, 216, 230)
+                    };
+                    ui.label(RichText::new(format!("{}", article.title)).color(color));
+                    let response = ui.hyperlink(article.url.to_string()).on_hover_text("Click to see the news");
+                    if response.clicked() {
+                        if let Err(e) = self.store.mark_read(&article.url) {
+                            self.last_error = Some(format!("Local store error: {}", e));
+                        }
+                    }
                     ui.separator();
                 }
             });
 
+            if !self.show_history {
+                if let Some(feed) = self.feeds.get(self.active_tab) {
+                    if let Some(total) = feed.total_results {
+                        ui.label(format!("{} of {} articles", feed.list_of_articles.len(), total));
+                    }
+                    // `FeedKind::Feed` has no concept of pages (`parse_feed` ignores `page`), and
+                    // there's nothing left to fetch once `total_results` says we have it all.
+                    let paginated = !matches!(feed.kind, FeedKind::Feed(_));
+                    let has_more = feed.total_results.map_or(false, |total| feed.list_of_articles.len() < total as usize);
+                    if paginated && has_more && ui.button("Load more").clicked() {
+                        self.channel_to_news_page.send((feed.kind.clone(), feed.current_page + 1)).unwrap();
+                    }
+                }
+            }
+
         });
     }
 }
 
-fn show_menu(ui: &mut egui::Ui, category_flag: &mut [bool; cardinality::<Categories>()], polling_interval: &mut u64, channel_to_news: &mut mpsc::Sender<NewsReportConfig>){
+// Returns true when the Settings menu changed any state the caller should fold
+// into a fresh `NewsReportConfig` and push to the fetcher.
+fn show_menu(ui: &mut egui::Ui, category_flag: &mut [bool; cardinality::<Categories>()], country: &mut Country, polling_interval: &mut u64, notify_flag: &mut [bool; cardinality::<Categories>()], quiet: &mut bool, search_query: &mut String, search_from: &mut String, search_to: &mut String, search_sort_by: &mut SortOrder, feed_url: &mut String) -> bool {
 
         use egui::{menu};
 
+        let mut is_update_required = false;
+
         menu::bar(ui, |ui| {
             ui.menu_button("Settings", |ui| {
-                let mut is_update_required = false;
-    
+
                 // Put items inside lambda to avoid creating them if the menu is not open
                 ui.menu_button("Categories", |ui: &mut egui::Ui| {
-                
+
                     let mut enum_counter = 0;
                     let mut selected_categories = category_flag.clone();
                     for category in all::<Categories>(){
@@ -137,54 +614,102 @@ fn show_menu(ui: &mut egui::Ui, category_flag: &mut [bool; cardinality::<Categor
                     }
                 });
 
-                if is_update_required {
-                    let mut counter = 0;
-                    let mut news_config = NewsReportConfig{
-                        selected_categories : vec![],
-                        polling_interval : *polling_interval,
-                    };
+                ui.menu_button( "Country", |ui: &mut egui::Ui| {
+                    let mut selected_country = *country;
+                    for candidate in all::<Country>(){
+                        ui.radio_value(&mut selected_country, candidate, candidate.to_string());
+                    }
+                    if selected_country != *country {
+                        *country = selected_country;
+                        is_update_required = true;
+                    }
+                });
+
+                ui.menu_button( "Notifications", |ui: &mut egui::Ui| {
+                    let mut requested_quiet = *quiet;
+                    ui.checkbox(&mut requested_quiet, "Quiet (disable all notifications)");
+                    if requested_quiet != *quiet {
+                        *quiet = requested_quiet;
+                        is_update_required = true;
+                    }
+
+                    ui.separator();
+
+                    let mut enum_counter = 0;
+                    let mut requested_notify_flag = notify_flag.clone();
                     for category in all::<Categories>(){
-                        if category_flag[counter] {
-                            news_config.selected_categories.push(category.to_string());
+                        ui.checkbox(&mut requested_notify_flag[enum_counter], category.to_string());
+                        enum_counter += 1;
+                    }
+                    if requested_notify_flag != *notify_flag {
+                        *notify_flag = requested_notify_flag;
+                        is_update_required = true;
+                    }
+                });
+
+                ui.menu_button( "Search", |ui: &mut egui::Ui| {
+                    let mut requested_query = search_query.clone();
+                    ui.text_edit_singleline(&mut requested_query).on_hover_text("Search keywords (leave empty to remove the search tab)");
+                    if requested_query != *search_query {
+                        *search_query = requested_query;
+                        is_update_required = true;
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("From");
+                        let mut requested_from = search_from.clone();
+                        ui.text_edit_singleline(&mut requested_from).on_hover_text("YYYY-MM-DD");
+                        if requested_from != *search_from {
+                            *search_from = requested_from;
+                            is_update_required = true;
                         }
-                        counter += 1;
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("To");
+                        let mut requested_to = search_to.clone();
+                        ui.text_edit_singleline(&mut requested_to).on_hover_text("YYYY-MM-DD");
+                        if requested_to != *search_to {
+                            *search_to = requested_to;
+                            is_update_required = true;
+                        }
+                    });
+
+                    ui.separator();
+
+                    let mut requested_sort_by = *search_sort_by;
+                    for order in all::<SortOrder>(){
+                        ui.radio_value(&mut requested_sort_by, order, order.to_string());
                     }
-                    channel_to_news.send(news_config.into()).unwrap();
-                    is_update_required = false;
-                }
-    
+                    if requested_sort_by != *search_sort_by {
+                        *search_sort_by = requested_sort_by;
+                        is_update_required = true;
+                    }
+                });
+
+                ui.menu_button( "Feed", |ui: &mut egui::Ui| {
+                    let mut requested_feed_url = feed_url.clone();
+                    ui.text_edit_singleline(&mut requested_feed_url).on_hover_text("RSS/Atom feed URL (leave empty to remove the feed tab)");
+                    if requested_feed_url != *feed_url {
+                        *feed_url = requested_feed_url;
+                        is_update_required = true;
+                    }
+                });
+
             });
-        
         });
+
+        is_update_required
     }
 
 fn main() {
 
-    let (from_gui, to_news) = mpsc::channel::<NewsReportConfig>();
-    let (from_news, to_gui) = mpsc::channel::<Articles>();
-
-    let news_thread_handle = thread::spawn(move || {
-        
-        let mut default_news_config = NewsReportConfig{
-            selected_categories : vec![],
-            polling_interval : 1,
-        };
+    let (from_gui, mut to_news) = async_mpsc::unbounded_channel::<NewsReportConfig>();
+    let (from_gui_page, mut to_news_page) = async_mpsc::unbounded_channel::<(FeedKind, u32)>();
+    let (from_news, to_gui) = mpsc::channel::<FeedUpdate>();
 
-        loop {
-            match to_news.try_recv() {
-                Ok(received_message) => {
-                    default_news_config = received_message;
-                },
-                _ => {}
-            }
-
-            let articles = get_articles( default_news_config.selected_categories.clone() );
- 
-            from_news.send(articles.unwrap()).unwrap();
-
-            thread::sleep(time::Duration::from_secs(default_news_config.polling_interval));
-        }
-    });
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start the async runtime");
+    let runtime_handle = runtime.handle().clone();
 
     let options = eframe::NativeOptions {
         transparent: false,
@@ -194,22 +719,180 @@ fn main() {
     };
 
 
-    let app = NewsReports{
-        category_flag: ([true;7]),
-        polling_interval: 600,
-        channel_to_news: from_gui.clone(),
-        channel_to_gui: to_gui,
-        list_of_articles: vec![]
-    };
+    let store = Store::open("news_reports.sqlite3").expect("failed to open the local article store");
 
     eframe::run_native(
         "News Reports",
         options,
-        Box::new(|_cc: &eframe::CreationContext| Box::<NewsReports>::new(app)),
+        Box::new(move |cc: &eframe::CreationContext| {
+            // The worker can only wake the repaint loop once it holds this context, so the
+            // fetch loop is spawned here rather than before `run_native` is called.
+            let repaint_ctx = cc.egui_ctx.clone();
+
+            runtime_handle.spawn(async move {
+
+                struct FeedState {
+                    def : FeedDef,
+                    last_fetched : Option<time::Instant>,
+                }
+
+                let mut config = NewsReportConfig{
+                    feeds : vec![FeedDef{ kind: FeedKind::General, polling_interval: 600 }],
+                    country : Country::Gb,
+                    quiet : false,
+                };
+                let mut feed_states : Vec<FeedState> = config.feeds.iter()
+                    .map(|def| FeedState{ def: def.clone(), last_fetched: None })
+                    .collect();
+
+                let mut ticker = tokio::time::interval(time::Duration::from_secs(1));
+
+                // Each fetch runs on its own task so a slow request can't stall this
+                // select loop, which would otherwise delay config updates and "Load more".
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => {
+                            let now = time::Instant::now();
+                            for feed in feed_states.iter_mut() {
+                                let due = feed.last_fetched.map_or(true, |last| now.duration_since(last).as_secs() >= feed.def.polling_interval);
+                                if !due {
+                                    continue;
+                                }
+                                feed.last_fetched = Some(now);
+                                let kind = feed.def.kind.clone();
+                                let country = config.country;
+                                let sender = from_news.clone();
+                                let ctx = repaint_ctx.clone();
+                                tokio::spawn(async move {
+                                    let result = fetch_feed(&kind, country, 1).await.map_err(|e| e.to_string());
+                                    if sender.send(FeedUpdate{ kind, page: 1, result }).is_ok() {
+                                        ctx.request_repaint();
+                                    }
+                                });
+                            }
+                        },
+                        received = to_news.recv() => {
+                            match received {
+                                Some(new_config) => {
+                                    feed_states = new_config.feeds.iter().map(|def| {
+                                        let last_fetched = feed_states.iter()
+                                            .find(|feed| feed.def.kind == def.kind)
+                                            .and_then(|feed| feed.last_fetched);
+                                        FeedState{ def: def.clone(), last_fetched }
+                                    }).collect();
+                                    config = new_config;
+                                },
+                                None => break,
+                            }
+                        },
+                        received_page = to_news_page.recv() => {
+                            match received_page {
+                                Some((kind, page)) => {
+                                    let country = config.country;
+                                    let sender = from_news.clone();
+                                    let ctx = repaint_ctx.clone();
+                                    tokio::spawn(async move {
+                                        let result = fetch_feed(&kind, country, page).await.map_err(|e| e.to_string());
+                                        if sender.send(FeedUpdate{ kind, page, result }).is_ok() {
+                                            ctx.request_repaint();
+                                        }
+                                    });
+                                },
+                                None => break,
+                            }
+                        },
+                    }
+                }
+            });
+
+            Box::<NewsReports>::new(NewsReports{
+                category_flag: ([true;7]),
+                country: Country::Gb,
+                polling_interval: 600,
+                notify_flag: ([false;7]),
+                quiet: false,
+                search_query: String::new(),
+                search_from: String::new(),
+                search_to: String::new(),
+                search_sort_by: SortOrder::PublishedAt,
+                feed_url: String::new(),
+                channel_to_news: from_gui.clone(),
+                channel_to_news_page: from_gui_page.clone(),
+                channel_to_gui: to_gui,
+                store,
+                feeds: vec![FeedTab{ kind: FeedKind::General, list_of_articles: vec![], current_page: 1, total_results: None }],
+                active_tab: 0,
+                show_history: false,
+                last_error: None,
+            })
+        }),
     );
 
-    let _res = news_thread_handle.join();
+    // Keep the runtime (and its background fetch task) alive for as long as `main` runs.
+    drop(runtime);
 
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_feed_xml_reads_rss_item() {
+        let rss = r#"<?xml version="1.0"?>
+            <rss version="2.0">
+              <channel>
+                <title>Example Feed</title>
+                <item>
+                  <title>Hello World</title>
+                  <link>https://example.com/hello</link>
+                </item>
+              </channel>
+            </rss>"#;
+
+        let articles = parse_feed_xml(rss).unwrap();
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].title, "Hello World");
+        assert_eq!(articles[0].url, "https://example.com/hello");
+    }
+
+    #[test]
+    fn parse_feed_xml_reads_atom_entry_with_self_closing_link() {
+        let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Example Atom Feed</title>
+              <entry>
+                <title>Atom Entry</title>
+                <link href="https://example.com/atom-entry"/>
+              </entry>
+            </feed>"#;
+
+        let articles = parse_feed_xml(atom).unwrap();
+        assert_eq!(articles.len(), 1);
+        assert_eq!(articles[0].title, "Atom Entry");
+        assert_eq!(articles[0].url, "https://example.com/atom-entry");
+    }
+
+    #[test]
+    fn build_newsapi_url_zz_country_defaults_query_to_news() {
+        let url = build_newsapi_url(&[], Country::Zz, 1, "KEY").unwrap();
+        assert_eq!(url.as_str(), "https://newsapi.org/v2/everything?q=news&apiKey=KEY&page=1");
+    }
+
+    #[test]
+    fn build_newsapi_url_zz_country_percent_encodes_categories() {
+        let categories = vec!["climate change".to_string()];
+        let url = build_newsapi_url(&categories, Country::Zz, 1, "KEY").unwrap();
+        assert_eq!(url.as_str(), "https://newsapi.org/v2/everything?q=climate+change&apiKey=KEY&page=1");
+    }
 
+    #[test]
+    fn build_newsapi_url_non_zz_country_uses_top_headlines_with_categories() {
+        let categories = vec!["business".to_string(), "tech news".to_string()];
+        let url = build_newsapi_url(&categories, Country::Gb, 2, "KEY").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://newsapi.org/v2/top-headlines?country=gb&apiKey=KEY&page=2&category=business&category=tech+news"
+        );
+    }
+}
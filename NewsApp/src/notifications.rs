@@ -0,0 +1,21 @@
+use notify_rust::Notification;
+use std::error::Error;
+
+use crate::storage::StoredArticle;
+
+// Best-effort source label pulled from the article URL's host, e.g.
+// "https://www.bbc.co.uk/news/x" -> "www.bbc.co.uk".
+fn source_from_url( url : &str ) -> String {
+    let without_scheme = url.splitn(2, "//").nth(1).unwrap_or(url);
+    without_scheme.split('/').next().unwrap_or(without_scheme).to_string()
+}
+
+pub fn notify_new_articles( articles : &[StoredArticle] ) -> Result<(), Box<dyn Error>> {
+    for article in articles {
+        Notification::new()
+            .summary(&article.title)
+            .body(&source_from_url(&article.url))
+            .show()?;
+    }
+    Ok(())
+}
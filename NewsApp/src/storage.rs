@@ -0,0 +1,79 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Article;
+
+#[derive(Debug, Clone)]
+pub struct StoredArticle {
+    pub title : String,
+    pub url : String,
+    pub first_seen : i64,
+    pub read : bool,
+}
+
+pub struct Store {
+    conn : Connection,
+}
+
+impl Store {
+    pub fn open(path : &str) -> SqlResult<Store> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS articles (
+                url TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                first_seen INTEGER NOT NULL,
+                read INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )?;
+        Ok(Store{ conn })
+    }
+
+    // Dedups on `url`; an article already in the store keeps its original
+    // `first_seen`/`read` state and is left untouched.
+    pub fn upsert(&self, articles : &[Article]) -> SqlResult<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+        for article in articles {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO articles (url, title, first_seen, read) VALUES (?1, ?2, ?3, 0)",
+                params![article.url, article.title, now],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn get( &self, url : &str ) -> SqlResult<Option<StoredArticle>> {
+        self.conn.query_row(
+            "SELECT url, title, first_seen, read FROM articles WHERE url = ?1",
+            params![url],
+            |row| Ok(StoredArticle{
+                url: row.get(0)?,
+                title: row.get(1)?,
+                first_seen: row.get(2)?,
+                read: row.get::<_, i64>(3)? != 0,
+            }),
+        ).map(Some).or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            other => Err(other),
+        })
+    }
+
+    pub fn all( &self ) -> SqlResult<Vec<StoredArticle>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT url, title, first_seen, read FROM articles ORDER BY first_seen DESC"
+        )?;
+        let rows = stmt.query_map([], |row| Ok(StoredArticle{
+            url: row.get(0)?,
+            title: row.get(1)?,
+            first_seen: row.get(2)?,
+            read: row.get::<_, i64>(3)? != 0,
+        }))?;
+        rows.collect()
+    }
+
+    pub fn mark_read( &self, url : &str ) -> SqlResult<()> {
+        self.conn.execute("UPDATE articles SET read = 1 WHERE url = ?1", params![url])?;
+        Ok(())
+    }
+}